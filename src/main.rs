@@ -1,32 +1,143 @@
-use std::{io, usize};
+use std::{
+    io,
+    sync::mpsc,
+    thread,
+    time::{Duration, Instant},
+    usize,
+};
 
 use crossterm::{
+    cursor::Show,
     event::{
-        self, DisableMouseCapture, EnableMouseCapture, Event, KeyCode, KeyEvent, KeyModifiers,
+        self, DisableMouseCapture, EnableMouseCapture, Event as CEvent, KeyCode, KeyEvent,
+        KeyModifiers,
     },
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
+use copypasta::{ClipboardContext, ClipboardProvider};
 use tui::{
     backend::{Backend, CrosstermBackend},
     layout::{Constraint, Direction, Layout, Rect},
     style::{Color, Modifier, Style},
-    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph},
+    text::Spans,
+    widgets::{Block, Borders, Clear, List, ListItem, ListState, Paragraph, Tabs},
     Frame, Terminal,
 };
 use unicode_width::UnicodeWidthStr;
 
+/// How often the input thread emits a `Tick` when no key is pressed.
+const TICK_RATE: Duration = Duration::from_millis(250);
+
+/// An event fed to the main loop: either a key from the input thread or a
+/// periodic tick used to drive time-based redraws.
+enum Event<I> {
+    Input(I),
+    Tick,
+}
+
 enum Mode {
     Normal,
     Insert,
     Popup,
+    Search,
+    Edit,
+}
+
+/// Foreground and accent colors used throughout `ui()`.
+#[derive(Clone, Copy)]
+struct Colors {
+    foreground: Color,
+    accent: Color,
+}
+
+impl Colors {
+    /// Built-in themes cycled through at runtime; the first is the default.
+    fn presets() -> Vec<Colors> {
+        vec![
+            Colors {
+                foreground: Color::Rgb(0xff, 0xff, 0xff),
+                accent: Color::Rgb(0xff, 0xd7, 0x00),
+            },
+            Colors {
+                foreground: Color::Rgb(0xff, 0xff, 0xff),
+                accent: Color::Rgb(0x00, 0xbf, 0xff),
+            },
+            Colors {
+                foreground: Color::Rgb(0xff, 0xff, 0xff),
+                accent: Color::Rgb(0x98, 0xfb, 0x98),
+            },
+        ]
+    }
+    /// Override the default preset from `--foreground`/`--accent` hex flags.
+    fn from_args() -> Colors {
+        let mut colors = Colors::presets()[0];
+        let args: Vec<String> = std::env::args().collect();
+        for pair in args.windows(2) {
+            match (pair[0].as_str(), parse_hex(&pair[1])) {
+                ("--foreground", Some(c)) => colors.foreground = c,
+                ("--accent", Some(c)) => colors.accent = c,
+                _ => {}
+            }
+        }
+        colors
+    }
+    /// A dimmed variant of the accent for unfocused blocks.
+    fn dimmed_accent(&self) -> Color {
+        dim(self.accent)
+    }
+}
+
+/// Parse a `#rrggbb` hex string into a `Color::Rgb`, returning `None` when the
+/// string is malformed.
+fn parse_hex(s: &str) -> Option<Color> {
+    let s = s.trim_start_matches('#');
+    if s.len() != 6 || !s.chars().all(|c| c.is_ascii_hexdigit()) {
+        return None;
+    }
+    let r = u8::from_str_radix(&s[0..2], 16).ok()?;
+    let g = u8::from_str_radix(&s[2..4], 16).ok()?;
+    let b = u8::from_str_radix(&s[4..6], 16).ok()?;
+    Some(Color::Rgb(r, g, b))
+}
+
+/// Scale each RGB channel by 0.5 (rounded) to derive a dimmed color. Non-RGB
+/// colors are returned unchanged.
+fn dim(color: Color) -> Color {
+    match color {
+        Color::Rgb(r, g, b) => Color::Rgb(
+            (r as f32 * 0.5).round() as u8,
+            (g as f32 * 0.5).round() as u8,
+            (b as f32 * 0.5).round() as u8,
+        ),
+        other => other,
+    }
+}
+
+struct TabsState {
+    titles: Vec<String>,
+    index: usize,
+}
+
+impl TabsState {
+    fn next(&mut self) {
+        self.index = (self.index + 1) % self.titles.len();
+    }
+    fn previous(&mut self) {
+        self.index = (self.index + self.titles.len() - 1) % self.titles.len();
+    }
 }
 
 struct App {
-    selected_list_index: Option<usize>,
-    items: Vec<String>,
+    tabs: TabsState,
+    selected_list_index: Vec<Option<usize>>,
+    items: Vec<Vec<String>>,
     input: String,
     mode: Mode,
+    search_query: String,
+    clipboard: Option<ClipboardContext>,
+    colors: Colors,
+    preset_index: usize,
 }
 
 impl App {
@@ -39,45 +150,141 @@ impl App {
     fn enter_popup_mode(&mut self) {
         self.mode = Mode::Popup;
     }
+    fn enter_search_mode(&mut self) {
+        self.mode = Mode::Search;
+    }
+    /// Load the selected item's text into the input buffer for editing.
+    fn enter_edit_mode(&mut self) {
+        if let Some(n) = self.selected() {
+            self.input = self.current_items()[n].clone();
+            self.mode = Mode::Edit;
+        }
+    }
+    /// Overwrite the selected item with the edited input buffer.
+    fn commit_edit(&mut self) {
+        if let Some(n) = self.selected() {
+            self.items[self.tabs.index][n] = self.input.drain(..).collect();
+        }
+        self.enter_normal_mode();
+    }
+    /// Preview the next built-in theme preset.
+    fn cycle_theme(&mut self) {
+        let presets = Colors::presets();
+        self.preset_index = (self.preset_index + 1) % presets.len();
+        self.colors = presets[self.preset_index];
+    }
+    /// The item list of the currently active tab.
+    fn current_items(&self) -> &Vec<String> {
+        &self.items[self.tabs.index]
+    }
+    /// The selected row of the currently active tab.
+    fn selected(&self) -> Option<usize> {
+        self.selected_list_index[self.tabs.index]
+    }
+    fn set_selected(&mut self, index: Option<usize>) {
+        self.selected_list_index[self.tabs.index] = index;
+    }
+    fn matches_query(&self, item: &str) -> bool {
+        self.search_query.is_empty()
+            || item
+                .to_lowercase()
+                .contains(&self.search_query.to_lowercase())
+    }
+    /// Real indices into the active tab's list of the rows currently visible
+    /// under the active search query, in list order (all rows when empty).
+    fn visible_indices(&self) -> Vec<usize> {
+        self.current_items()
+            .iter()
+            .enumerate()
+            .filter(|(_, item)| self.matches_query(item))
+            .map(|(i, _)| i)
+            .collect()
+    }
     fn select_next(&mut self) {
-        match self.selected_list_index {
+        let visible = self.visible_indices();
+        if visible.is_empty() {
+            return;
+        }
+        match self.selected() {
             Some(n) => {
-                if n != self.items.len() - 1 {
-                    self.selected_list_index = Some(n + 1);
-                } else {
-                    self.selected_list_index = Some(0);
-                }
+                let pos = visible.iter().position(|&i| i == n).unwrap_or(0);
+                let next = if pos != visible.len() - 1 { pos + 1 } else { 0 };
+                self.set_selected(Some(visible[next]));
             }
             None => {}
         }
     }
     fn select_previous(&mut self) {
-        match self.selected_list_index {
+        let visible = self.visible_indices();
+        if visible.is_empty() {
+            return;
+        }
+        match self.selected() {
             Some(n) => {
-                if n > 0 {
-                    self.selected_list_index = Some(n - 1);
-                } else {
-                    self.selected_list_index = Some(self.items.len() - 1);
-                }
+                let pos = visible.iter().position(|&i| i == n).unwrap_or(0);
+                let prev = if pos > 0 { pos - 1 } else { visible.len() - 1 };
+                self.set_selected(Some(visible[prev]));
             }
             None => {}
         }
     }
     fn push_input_to_items(&mut self) {
-        self.items.push(self.input.drain(..).collect());
-        if self.selected_list_index == None {
-            self.selected_list_index = Some(0)
+        let item = self.input.drain(..).collect();
+        self.items[self.tabs.index].push(item);
+        if self.selected() == None {
+            self.set_selected(Some(0))
         }
         self.enter_normal_mode();
     }
+    /// Copy the selected item's text to the system clipboard, if one is
+    /// available. A no-op when no item is selected or no backend exists.
+    fn yank_selected_item(&mut self) {
+        if let Some(n) = self.selected() {
+            let text = self.current_items()[n].clone();
+            if let Some(clip) = self.clipboard.as_mut() {
+                let _ = clip.set_contents(text);
+            }
+        }
+    }
+    /// Read the system clipboard and push its contents as a new item, reusing
+    /// the `push_input_to_items` path. A no-op when no backend is available.
+    fn paste_as_item(&mut self) {
+        let text = self
+            .clipboard
+            .as_mut()
+            .and_then(|clip| clip.get_contents().ok());
+        if let Some(text) = text {
+            self.input = text;
+            self.push_input_to_items();
+        }
+    }
+    /// Paste the system clipboard into the input buffer. A no-op when no
+    /// backend is available.
+    fn paste_into_input(&mut self) {
+        let text = self
+            .clipboard
+            .as_mut()
+            .and_then(|clip| clip.get_contents().ok());
+        if let Some(text) = text {
+            self.input.push_str(&text);
+        }
+    }
     fn delete_selected_item(&mut self) {
-        match self.selected_list_index {
+        match self.selected() {
             Some(n) => {
-                self.items.remove(n);
-                if self.items.is_empty() {
-                    self.selected_list_index = None
-                } else if self.items.len() == n {
-                    self.select_previous();
+                self.items[self.tabs.index].remove(n);
+                let visible = self.visible_indices();
+                if visible.is_empty() {
+                    self.set_selected(None)
+                } else {
+                    // Keep the cursor on the nearest remaining visible row.
+                    let target = visible
+                        .iter()
+                        .rev()
+                        .find(|&&i| i <= n)
+                        .copied()
+                        .unwrap_or(visible[0]);
+                    self.set_selected(Some(target));
                 }
             }
             None => {}
@@ -88,10 +295,21 @@ impl App {
 impl Default for App {
     fn default() -> Self {
         App {
-            selected_list_index: Some(1),
-            items: vec!["aaa".to_string(), "bbb".to_string(), "ccc".to_string()],
+            tabs: TabsState {
+                titles: vec!["Open".to_string(), "Done".to_string()],
+                index: 0,
+            },
+            selected_list_index: vec![Some(1), None],
+            items: vec![
+                vec!["aaa".to_string(), "bbb".to_string(), "ccc".to_string()],
+                vec![],
+            ],
             input: String::new(),
             mode: Mode::Normal,
+            search_query: String::new(),
+            clipboard: ClipboardContext::new().ok(),
+            colors: Colors::presets()[0],
+            preset_index: 0,
         }
     }
 }
@@ -103,15 +321,40 @@ fn main() -> Result<(), io::Error> {
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
     let mut app = App::default();
+    app.colors = Colors::from_args();
+
+    let original_hook = std::panic::take_hook();
+    std::panic::set_hook(Box::new(move |info| {
+        let _ = restore_terminal();
+        original_hook(info);
+    }));
+
+    let (tx, rx) = mpsc::channel();
+    thread::spawn(move || {
+        let mut last_tick = Instant::now();
+        loop {
+            let timeout = TICK_RATE
+                .checked_sub(last_tick.elapsed())
+                .unwrap_or_else(|| Duration::from_secs(0));
+            if event::poll(timeout).unwrap() {
+                if let CEvent::Key(key) = event::read().unwrap() {
+                    tx.send(Event::Input(key)).unwrap();
+                }
+            }
+            if last_tick.elapsed() >= TICK_RATE && tx.send(Event::Tick).is_ok() {
+                last_tick = Instant::now();
+            }
+        }
+    });
 
     loop {
         terminal.draw(|f| {
             ui(f, &app);
         })?;
 
-        if let Event::Key(KeyEvent {
+        if let Event::Input(KeyEvent {
             code, modifiers, ..
-        }) = event::read()?
+        }) = rx.recv().unwrap()
         {
             match app.mode {
                 Mode::Insert => match (code, modifiers) {
@@ -127,23 +370,38 @@ fn main() -> Result<(), io::Error> {
                     (KeyCode::Backspace, KeyModifiers::NONE) => {
                         app.input.pop();
                     }
+                    (KeyCode::Char('v'), KeyModifiers::CONTROL) => {
+                        app.paste_into_input();
+                    }
+                    _ => {}
+                },
+                Mode::Edit => match (code, modifiers) {
+                    (KeyCode::Esc, KeyModifiers::NONE) => {
+                        app.input.clear();
+                        app.enter_normal_mode();
+                    }
+                    (KeyCode::Enter, KeyModifiers::NONE) => {
+                        app.commit_edit();
+                    }
+                    (KeyCode::Char(c), KeyModifiers::NONE) => {
+                        app.input.push(c);
+                    }
+                    (KeyCode::Backspace, KeyModifiers::NONE) => {
+                        app.input.pop();
+                    }
                     _ => {}
                 },
                 Mode::Normal => match (code, modifiers) {
                     (KeyCode::Esc, KeyModifiers::NONE) => {
-                        disable_raw_mode()?;
-                        execute!(
-                            terminal.backend_mut(),
-                            LeaveAlternateScreen,
-                            DisableMouseCapture
-                        )?;
-
-                        terminal.show_cursor()?;
+                        restore_terminal()?;
                         return Ok(());
                     }
                     (KeyCode::Char('i'), KeyModifiers::NONE) => {
                         app.enter_insert_mode();
                     }
+                    (KeyCode::Char('e'), KeyModifiers::NONE) => {
+                        app.enter_edit_mode();
+                    }
                     (KeyCode::Char('j'), KeyModifiers::NONE) => {
                         app.select_next();
                     }
@@ -153,9 +411,45 @@ fn main() -> Result<(), io::Error> {
                     (KeyCode::Char('d'), KeyModifiers::NONE) => {
                         app.delete_selected_item();
                     }
+                    (KeyCode::Char('y'), KeyModifiers::NONE) => {
+                        app.yank_selected_item();
+                    }
+                    (KeyCode::Char('p'), KeyModifiers::NONE) => {
+                        app.paste_as_item();
+                    }
                     (KeyCode::Char('?'), KeyModifiers::NONE) => {
                         app.enter_popup_mode();
                     }
+                    (KeyCode::Char('/'), KeyModifiers::NONE) => {
+                        app.enter_search_mode();
+                    }
+                    (KeyCode::Char('t'), KeyModifiers::NONE) => {
+                        app.cycle_theme();
+                    }
+                    (KeyCode::Tab, KeyModifiers::NONE)
+                    | (KeyCode::Char('l'), KeyModifiers::NONE) => {
+                        app.tabs.next();
+                    }
+                    (KeyCode::BackTab, KeyModifiers::SHIFT)
+                    | (KeyCode::Char('h'), KeyModifiers::NONE) => {
+                        app.tabs.previous();
+                    }
+                    _ => {}
+                },
+                Mode::Search => match (code, modifiers) {
+                    (KeyCode::Esc, KeyModifiers::NONE) => {
+                        app.search_query.clear();
+                        app.enter_normal_mode();
+                    }
+                    (KeyCode::Enter, KeyModifiers::NONE) => {
+                        app.enter_normal_mode();
+                    }
+                    (KeyCode::Char(c), KeyModifiers::NONE) => {
+                        app.search_query.push(c);
+                    }
+                    (KeyCode::Backspace, KeyModifiers::NONE) => {
+                        app.search_query.pop();
+                    }
                     _ => {}
                 },
                 Mode::Popup => match (code, modifiers) {
@@ -175,12 +469,19 @@ fn main() -> Result<(), io::Error> {
     }
 }
 
+fn restore_terminal() -> Result<(), io::Error> {
+    disable_raw_mode()?;
+    execute!(io::stdout(), LeaveAlternateScreen, DisableMouseCapture, Show)?;
+    Ok(())
+}
+
 fn ui<B: Backend>(f: &mut Frame<B>, app: &App) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
         .margin(1)
         .constraints(
             [
+                Constraint::Length(3),
                 Constraint::Percentage(10),
                 Constraint::Percentage(80),
                 Constraint::Percentage(10),
@@ -189,39 +490,75 @@ fn ui<B: Backend>(f: &mut Frame<B>, app: &App) {
         )
         .split(f.size());
 
-    let input = Paragraph::new(app.input.as_ref())
-        .style(match app.mode {
-            Mode::Insert => Style::default().fg(Color::Yellow),
-            _ => Style::default(),
-        })
-        .block(Block::default().borders(Borders::ALL).title("Input"));
-    f.render_widget(input, chunks[0]);
+    let accent = app.colors.accent;
+    let dimmed = app.colors.dimmed_accent();
+
+    let titles = app
+        .tabs
+        .titles
+        .iter()
+        .map(|t| Spans::from(t.as_ref()))
+        .collect();
+    let tabs = Tabs::new(titles)
+        .block(Block::default().borders(Borders::ALL).title("Tabs"))
+        .select(app.tabs.index)
+        .style(Style::default().fg(app.colors.foreground))
+        .highlight_style(Style::default().fg(accent));
+    f.render_widget(tabs, chunks[0]);
+
+    let input = Paragraph::new(match app.mode {
+        Mode::Search => app.search_query.as_ref(),
+        _ => app.input.as_ref(),
+    })
+    .style(match app.mode {
+        Mode::Insert => Style::default().fg(accent),
+        Mode::Search => Style::default().fg(accent),
+        Mode::Edit => Style::default().fg(accent),
+        _ => Style::default().fg(dimmed),
+    })
+    .block(Block::default().borders(Borders::ALL).title(match app.mode {
+        Mode::Edit => "Edit",
+        _ => "Input",
+    }));
+    f.render_widget(input, chunks[1]);
     let block2 = Block::default().title("block2").borders(Borders::ALL);
-    f.render_widget(block2, chunks[2]);
+    f.render_widget(block2, chunks[3]);
 
-    let items2 = app
-        .items
+    let visible = app.visible_indices();
+    let current_items = app.current_items();
+    let items2 = visible
         .iter()
-        .map(|item| ListItem::new(item.to_string()))
+        .map(|&i| ListItem::new(current_items[i].to_string()))
         .collect::<Vec<ListItem>>();
 
     let list = List::new(items2)
         .block(Block::default().title("List").borders(Borders::ALL))
         .style(match app.mode {
-            Mode::Normal => Style::default().fg(Color::Yellow),
-            _ => Style::default(),
+            Mode::Normal => Style::default().fg(accent),
+            _ => Style::default().fg(dimmed),
         })
-        .highlight_style(Style::default().add_modifier(Modifier::ITALIC))
+        .highlight_style(
+            Style::default()
+                .fg(accent)
+                .add_modifier(Modifier::ITALIC),
+        )
         .highlight_symbol(">>");
     let mut state = ListState::default();
-    state.select(app.selected_list_index);
-    f.render_stateful_widget(list, chunks[1], &mut state);
+    state.select(
+        app.selected()
+            .and_then(|n| visible.iter().position(|&i| i == n)),
+    );
+    f.render_stateful_widget(list, chunks[2], &mut state);
 
     match app.mode {
         Mode::Normal => {}
-        Mode::Insert => f.set_cursor(
-            chunks[0].x + app.input.width_cjk() as u16 + 1,
-            chunks[0].y + 1,
+        Mode::Insert | Mode::Edit => f.set_cursor(
+            chunks[1].x + app.input.width_cjk() as u16 + 1,
+            chunks[1].y + 1,
+        ),
+        Mode::Search => f.set_cursor(
+            chunks[1].x + app.search_query.width_cjk() as u16 + 1,
+            chunks[1].y + 1,
         ),
         Mode::Popup => {
             let block =
@@ -229,7 +566,7 @@ fn ui<B: Backend>(f: &mut Frame<B>, app: &App) {
                     .title("Popup")
                     .borders(Borders::ALL)
                     .style(match app.mode {
-                        Mode::Popup => Style::default().fg(Color::Yellow),
+                        Mode::Popup => Style::default().fg(accent),
                         _ => Style::default(),
                     });
             let area = centered_rect(60, 20, f.size());